@@ -0,0 +1,3 @@
+pub mod order_book;
+pub mod price;
+pub mod types;
@@ -0,0 +1,311 @@
+use std::collections::VecDeque;
+use std::num::NonZeroU64;
+
+use super::{BookError, Capacity, ModifyOutcome, PriceLevel, PriceLevels, aggregate, bucket};
+use crate::types::{Order, Side};
+
+/// Sell side of an order book.
+///
+/// Mirrors [`BidBook`](super::BidBook), except the lowest price is the best
+/// ask. Orders within a level keep their arrival (FIFO) order.
+#[derive(Debug)]
+pub struct AskBook {
+    inner: PriceLevels,
+}
+
+impl AskBook {
+    pub fn new() -> Self {
+        Self {
+            inner: PriceLevels::new(Side::Sell),
+        }
+    }
+
+    /// A book that rejects new resting orders once `max_orders` are queued.
+    ///
+    /// The cap covers this side only: a symbol whose [`BidBook`](super::BidBook)
+    /// is built with the same cap can hold up to `2 * max_orders` orders.
+    pub fn with_max_orders(max_orders: usize) -> Self {
+        Self {
+            inner: PriceLevels::with_max_orders(Side::Sell, max_orders),
+        }
+    }
+
+    /// Queues `order` at the back of its price level. Rejects an order that is
+    /// not a sell, a zero quantity, an id that is already resting, an order
+    /// past the book's `max_orders` cap, or a quantity that would push the
+    /// side's total past `u64::MAX`, leaving the book unchanged.
+    pub fn add_order(&mut self, order: Order) -> Result<(), BookError> {
+        self.inner.add(order)
+    }
+
+    /// Removes the order with `id`, dropping its price level if it empties.
+    pub fn remove_order(&mut self, id: u64) -> Option<Order> {
+        self.inner.remove(id)
+    }
+
+    /// Amends a resting order's price and/or quantity.
+    ///
+    /// A pure quantity decrease keeps the order's place in its queue. A price
+    /// change or quantity increase loses time priority and re-queues the order
+    /// at the back of its (new) level. A zero quantity cancels the order.
+    /// Fails with [`BookError::UnknownId`] if no order with `id` is resting.
+    pub fn modify_order(
+        &mut self,
+        id: u64,
+        new_price: Option<u64>,
+        new_quantity: Option<u64>,
+    ) -> Result<ModifyOutcome<'_>, BookError> {
+        self.inner.modify(id, new_price, new_quantity)
+    }
+
+    /// Oldest order at the lowest price, if any.
+    pub fn get_best_ask(&self) -> Option<&Order> {
+        self.levels().next().and_then(|(_, level)| level.front())
+    }
+
+    pub fn total_quantity_at(&self, price: u64) -> u64 {
+        self.inner.total_quantity_at(price)
+    }
+
+    /// Price levels from best (lowest) to worst.
+    pub fn levels(&self) -> impl Iterator<Item = (u64, &VecDeque<Order>)> {
+        self.inner.ascending()
+    }
+
+    /// Aggregated quantity for the top `n` levels, lowest price first.
+    pub fn depth(&self, n: usize) -> Vec<PriceLevel> {
+        aggregate(self.levels(), n)
+    }
+
+    /// Aggregated quantity per price bucket of `width` ticks, lowest bucket
+    /// first. Each price falls into the bucket at or below it, i.e. the
+    /// bucket's price is `price - price % width`.
+    pub fn bucketed_depth(&self, width: NonZeroU64) -> Vec<PriceLevel> {
+        bucket(self.levels(), width)
+    }
+
+    /// Verifies the book's structure: no empty levels, no zero-quantity or
+    /// wrong-side orders, every order queued under its own price, unique ids,
+    /// an id index that matches the levels exactly, and a running side total
+    /// that matches the resting quantities.
+    pub fn check_invariants(&self) -> Result<(), BookError> {
+        self.inner.check_invariants()
+    }
+
+    /// Slots currently reserved by the price levels and the id index.
+    pub fn capacity(&self) -> Capacity {
+        self.inner.capacity()
+    }
+
+    /// Releases spare capacity left behind by transient spikes. Any level (and
+    /// the id index) using less than `min_utilization_pct` percent of its
+    /// reserved slots is shrunk to fit; values above 100 are treated as 100.
+    /// Returns the slots reclaimed from each.
+    pub fn compact(&mut self, min_utilization_pct: u8) -> Capacity {
+        self.inner.compact(min_utilization_pct)
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for AskBook {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::order_book::fixtures::{ask, bid};
+
+    fn ids_at(book: &AskBook, price: u64) -> Vec<u64> {
+        book.levels()
+            .find(|(p, _)| *p == price)
+            .map(|(_, level)| level.iter().map(|o| o.id).collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn empty_book_has_no_best_ask() {
+        let book = AskBook::new();
+        assert!(book.get_best_ask().is_none());
+        assert_eq!(book.total_quantity_at(100), 0);
+    }
+
+    #[test]
+    fn interleaved_inserts_keep_time_priority() {
+        let mut book = AskBook::new();
+        book.add_order(ask(1, 100, 10)).unwrap();
+        book.add_order(ask(2, 101, 10)).unwrap();
+        book.add_order(ask(3, 100, 20)).unwrap();
+        book.add_order(ask(4, 101, 20)).unwrap();
+        book.add_order(ask(5, 100, 30)).unwrap();
+
+        assert_eq!(ids_at(&book, 100), vec![1, 3, 5]);
+        assert_eq!(ids_at(&book, 101), vec![2, 4]);
+        assert_eq!(book.total_quantity_at(100), 60);
+
+        book.remove_order(3);
+        assert_eq!(ids_at(&book, 100), vec![1, 5]);
+        assert_eq!(book.get_best_ask().unwrap().id, 1);
+    }
+
+    #[test]
+    fn levels_iterate_cheapest_first() {
+        let mut book = AskBook::new();
+        book.add_order(ask(1, 102, 10)).unwrap();
+        book.add_order(ask(2, 100, 10)).unwrap();
+        book.add_order(ask(3, 101, 10)).unwrap();
+
+        assert_eq!(book.get_best_ask().unwrap().price, 100);
+        let prices: Vec<u64> = book.levels().map(|(p, _)| p).collect();
+        assert_eq!(prices, vec![100, 101, 102]);
+    }
+
+    #[test]
+    fn removing_last_order_drops_level() {
+        let mut book = AskBook::new();
+        book.add_order(ask(1, 100, 10)).unwrap();
+        book.add_order(ask(2, 101, 10)).unwrap();
+
+        book.remove_order(1);
+        assert_eq!(book.levels().count(), 1);
+        assert_eq!(book.get_best_ask().unwrap().price, 101);
+
+        book.remove_order(2);
+        assert!(book.is_empty());
+        assert!(book.get_best_ask().is_none());
+    }
+
+    #[test]
+    fn modify_applies_time_priority_rules() {
+        let mut book = AskBook::new();
+        book.add_order(ask(1, 100, 10)).unwrap();
+        book.add_order(ask(2, 100, 10)).unwrap();
+        book.add_order(ask(3, 99, 10)).unwrap();
+
+        book.modify_order(2, None, Some(5)).unwrap();
+        assert_eq!(ids_at(&book, 100), vec![1, 2]);
+
+        book.modify_order(1, Some(99), None).unwrap();
+        assert_eq!(ids_at(&book, 99), vec![3, 1]);
+        assert_eq!(ids_at(&book, 100), vec![2]);
+        assert_eq!(book.total_quantity_at(100), 5);
+
+        book.modify_order(3, None, Some(20)).unwrap();
+        assert_eq!(ids_at(&book, 99), vec![1, 3]);
+
+        assert!(matches!(
+            book.modify_order(2, None, Some(0)),
+            Ok(ModifyOutcome::Cancelled(Order { id: 2, .. }))
+        ));
+        assert_eq!(book.levels().count(), 1);
+        assert_eq!(
+            book.modify_order(2, None, Some(1)),
+            Err(BookError::UnknownId { id: 2 })
+        );
+    }
+
+    #[test]
+    fn depth_lists_cheapest_levels_first() {
+        let mut book = AskBook::new();
+        book.add_order(ask(1, 102, 4)).unwrap();
+        book.add_order(ask(2, 100, 6)).unwrap();
+        book.add_order(ask(3, 100, 2)).unwrap();
+
+        assert_eq!(
+            book.depth(1),
+            vec![PriceLevel {
+                price: 100,
+                quantity: 8
+            }]
+        );
+    }
+
+    #[test]
+    fn invariant_checker_catches_empty_level() {
+        let mut book = AskBook::new();
+        book.add_order(ask(1, 100, 10)).unwrap();
+        assert_eq!(book.check_invariants(), Ok(()));
+
+        book.inner.levels.entry(101).or_default();
+        assert_eq!(
+            book.check_invariants(),
+            Err(BookError::EmptyLevel { price: 101 })
+        );
+    }
+
+    #[test]
+    fn bucketed_depth_lists_cheapest_bucket_first() {
+        let mut book = AskBook::new();
+        book.add_order(ask(1, 120, 3)).unwrap();
+        book.add_order(ask(2, 99, 1)).unwrap();
+        book.add_order(ask(3, 100, 2)).unwrap();
+        book.add_order(ask(4, 119, 5)).unwrap();
+
+        assert_eq!(
+            book.bucketed_depth(NonZeroU64::new(10).unwrap()),
+            vec![
+                PriceLevel {
+                    price: 90,
+                    quantity: 1
+                },
+                PriceLevel {
+                    price: 100,
+                    quantity: 2
+                },
+                PriceLevel {
+                    price: 110,
+                    quantity: 5
+                },
+                PriceLevel {
+                    price: 120,
+                    quantity: 3
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn compaction_reclaims_drained_capacity() {
+        let mut book = AskBook::new();
+        for id in 0..500 {
+            book.add_order(ask(id, 100 + id % 2, 1)).unwrap();
+        }
+        for id in 0..490 {
+            book.remove_order(id);
+        }
+        let grown = book.capacity();
+
+        let reclaimed = book.compact(50);
+        let after = book.capacity();
+        assert!(reclaimed.level_slots > 0);
+        assert!(reclaimed.index_slots > 0);
+        assert_eq!(after.level_slots, grown.level_slots - reclaimed.level_slots);
+        assert_eq!(after.index_slots, grown.index_slots - reclaimed.index_slots);
+        assert!(after.level_slots < 50);
+
+        assert_eq!(ids_at(&book, 100), vec![490, 492, 494, 496, 498]);
+        assert_eq!(book.get_best_ask().unwrap().id, 490);
+        assert_eq!(book.check_invariants(), Ok(()));
+    }
+
+    #[test]
+    fn rejects_buy_orders() {
+        let mut book = AskBook::new();
+        assert_eq!(
+            book.add_order(bid(1, 100, 5)),
+            Err(BookError::WrongSide { id: 1 })
+        );
+        assert!(book.is_empty());
+        assert!(book.get_best_ask().is_none());
+        assert_eq!(book.check_invariants(), Ok(()));
+    }
+}
@@ -0,0 +1,97 @@
+use super::{AskBook, BidBook, PriceLevel};
+
+/// Best bid and offer: the aggregated top level of each side, `None` when
+/// that side is empty.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Bbo {
+    pub bid: Option<PriceLevel>,
+    pub ask: Option<PriceLevel>,
+}
+
+/// Remembers the last published top of book and reports it again only when
+/// the best price or its total quantity changes on either side. Changes to
+/// deeper levels are ignored.
+#[derive(Debug, Default)]
+pub struct BboTracker {
+    last: Bbo,
+}
+
+impl BboTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the new top of book if it differs from the last one reported.
+    pub fn update(&mut self, bids: &BidBook, asks: &AskBook) -> Option<Bbo> {
+        let bbo = Bbo {
+            bid: bids.depth(1).first().copied(),
+            ask: asks.depth(1).first().copied(),
+        };
+        if bbo == self.last {
+            return None;
+        }
+        self.last = bbo;
+        Some(bbo)
+    }
+
+    /// The top of book as last reported.
+    pub fn last(&self) -> Bbo {
+        self.last
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::order_book::fixtures::{ask, bid};
+
+    fn level(price: u64, quantity: u64) -> Option<PriceLevel> {
+        Some(PriceLevel { price, quantity })
+    }
+
+    #[test]
+    fn fires_only_when_the_top_changes() {
+        let mut bids = BidBook::new();
+        let mut asks = AskBook::new();
+        let mut tracker = BboTracker::new();
+        assert_eq!(tracker.update(&bids, &asks), None);
+
+        bids.add_order(bid(1, 100, 10)).unwrap();
+        assert_eq!(
+            tracker.update(&bids, &asks),
+            Some(Bbo {
+                bid: level(100, 10),
+                ask: None
+            })
+        );
+        asks.add_order(ask(2, 102, 5)).unwrap();
+        assert_eq!(tracker.update(&bids, &asks).unwrap().ask, level(102, 5));
+
+        // Deeper levels come and go without an event.
+        bids.add_order(bid(3, 99, 7)).unwrap();
+        asks.add_order(ask(4, 103, 7)).unwrap();
+        assert_eq!(tracker.update(&bids, &asks), None);
+        bids.modify_order(3, Some(98), Some(1)).unwrap();
+        asks.remove_order(4);
+        assert_eq!(tracker.update(&bids, &asks), None);
+
+        // Same best price, different quantity.
+        bids.add_order(bid(5, 100, 4)).unwrap();
+        assert_eq!(tracker.update(&bids, &asks).unwrap().bid, level(100, 14));
+
+        // A better ask, then the top level emptying.
+        asks.add_order(ask(6, 101, 1)).unwrap();
+        assert_eq!(tracker.update(&bids, &asks).unwrap().ask, level(101, 1));
+        bids.remove_order(1);
+        bids.remove_order(5);
+        assert_eq!(
+            tracker.update(&bids, &asks),
+            Some(Bbo {
+                bid: level(98, 1),
+                ask: level(101, 1)
+            })
+        );
+        assert_eq!(tracker.update(&bids, &asks), None);
+        assert_eq!(tracker.last().bid, level(98, 1));
+    }
+}
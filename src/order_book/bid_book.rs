@@ -0,0 +1,422 @@
+use std::collections::VecDeque;
+use std::num::NonZeroU64;
+
+use super::{BookError, Capacity, ModifyOutcome, PriceLevel, PriceLevels, aggregate, bucket};
+use crate::types::{Order, Side};
+
+/// Buy side of an order book.
+///
+/// Orders are grouped into price levels; the highest price is the best bid and
+/// orders within a level keep their arrival (FIFO) order.
+#[derive(Debug)]
+pub struct BidBook {
+    inner: PriceLevels,
+}
+
+impl BidBook {
+    pub fn new() -> Self {
+        Self {
+            inner: PriceLevels::new(Side::Buy),
+        }
+    }
+
+    /// A book that rejects new resting orders once `max_orders` are queued.
+    ///
+    /// The cap covers this side only: a symbol whose [`AskBook`](super::AskBook)
+    /// is built with the same cap can hold up to `2 * max_orders` orders.
+    pub fn with_max_orders(max_orders: usize) -> Self {
+        Self {
+            inner: PriceLevels::with_max_orders(Side::Buy, max_orders),
+        }
+    }
+
+    /// Queues `order` at the back of its price level. Rejects an order that is
+    /// not a buy, a zero quantity, an id that is already resting, an order
+    /// past the book's `max_orders` cap, or a quantity that would push the
+    /// side's total past `u64::MAX`, leaving the book unchanged.
+    pub fn add_order(&mut self, order: Order) -> Result<(), BookError> {
+        self.inner.add(order)
+    }
+
+    /// Removes the order with `id`, dropping its price level if it empties.
+    pub fn remove_order(&mut self, id: u64) -> Option<Order> {
+        self.inner.remove(id)
+    }
+
+    /// Amends a resting order's price and/or quantity.
+    ///
+    /// A pure quantity decrease keeps the order's place in its queue. A price
+    /// change or quantity increase loses time priority and re-queues the order
+    /// at the back of its (new) level. A zero quantity cancels the order.
+    /// Fails with [`BookError::UnknownId`] if no order with `id` is resting.
+    pub fn modify_order(
+        &mut self,
+        id: u64,
+        new_price: Option<u64>,
+        new_quantity: Option<u64>,
+    ) -> Result<ModifyOutcome<'_>, BookError> {
+        self.inner.modify(id, new_price, new_quantity)
+    }
+
+    /// Oldest order at the highest price, if any.
+    pub fn get_best_bid(&self) -> Option<&Order> {
+        self.levels().next().and_then(|(_, level)| level.front())
+    }
+
+    pub fn total_quantity_at(&self, price: u64) -> u64 {
+        self.inner.total_quantity_at(price)
+    }
+
+    /// Price levels from best (highest) to worst.
+    pub fn levels(&self) -> impl Iterator<Item = (u64, &VecDeque<Order>)> {
+        self.inner.ascending().rev()
+    }
+
+    /// Aggregated quantity for the top `n` levels, highest price first.
+    pub fn depth(&self, n: usize) -> Vec<PriceLevel> {
+        aggregate(self.levels(), n)
+    }
+
+    /// Aggregated quantity per price bucket of `width` ticks, highest bucket
+    /// first. Each price falls into the bucket at or below it, i.e. the
+    /// bucket's price is `price - price % width`.
+    pub fn bucketed_depth(&self, width: NonZeroU64) -> Vec<PriceLevel> {
+        bucket(self.levels(), width)
+    }
+
+    /// Verifies the book's structure: no empty levels, no zero-quantity or
+    /// wrong-side orders, every order queued under its own price, unique ids,
+    /// an id index that matches the levels exactly, and a running side total
+    /// that matches the resting quantities.
+    pub fn check_invariants(&self) -> Result<(), BookError> {
+        self.inner.check_invariants()
+    }
+
+    /// Slots currently reserved by the price levels and the id index.
+    pub fn capacity(&self) -> Capacity {
+        self.inner.capacity()
+    }
+
+    /// Releases spare capacity left behind by transient spikes. Any level (and
+    /// the id index) using less than `min_utilization_pct` percent of its
+    /// reserved slots is shrunk to fit; values above 100 are treated as 100.
+    /// Returns the slots reclaimed from each.
+    pub fn compact(&mut self, min_utilization_pct: u8) -> Capacity {
+        self.inner.compact(min_utilization_pct)
+    }
+
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for BidBook {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::order_book::fixtures::{ask, bid};
+
+    #[test]
+    fn empty_book_has_no_best_bid() {
+        let book = BidBook::new();
+        assert!(book.get_best_bid().is_none());
+        assert_eq!(book.total_quantity_at(100), 0);
+    }
+
+    #[test]
+    fn fifo_within_price_level() {
+        let mut book = BidBook::new();
+        book.add_order(bid(1, 100, 10)).unwrap();
+        book.add_order(bid(2, 100, 20)).unwrap();
+        book.add_order(bid(3, 100, 30)).unwrap();
+
+        let ids: Vec<u64> = book
+            .levels()
+            .next()
+            .unwrap()
+            .1
+            .iter()
+            .map(|o| o.id)
+            .collect();
+        assert_eq!(ids, vec![1, 2, 3]);
+        assert_eq!(book.get_best_bid().unwrap().id, 1);
+        assert_eq!(book.total_quantity_at(100), 60);
+
+        book.remove_order(1);
+        assert_eq!(book.get_best_bid().unwrap().id, 2);
+    }
+
+    #[test]
+    fn best_bid_is_highest_price() {
+        let mut book = BidBook::new();
+        book.add_order(bid(1, 99, 10)).unwrap();
+        book.add_order(bid(2, 101, 10)).unwrap();
+        book.add_order(bid(3, 100, 10)).unwrap();
+
+        assert_eq!(book.get_best_bid().unwrap().price, 101);
+        let prices: Vec<u64> = book.levels().map(|(p, _)| p).collect();
+        assert_eq!(prices, vec![101, 100, 99]);
+    }
+
+    #[test]
+    fn removing_last_order_drops_level() {
+        let mut book = BidBook::new();
+        book.add_order(bid(1, 101, 10)).unwrap();
+        book.add_order(bid(2, 100, 10)).unwrap();
+
+        assert_eq!(book.remove_order(1).unwrap().id, 1);
+        assert_eq!(book.levels().count(), 1);
+        assert_eq!(book.get_best_bid().unwrap().price, 100);
+        assert!(book.remove_order(1).is_none());
+
+        book.remove_order(2);
+        assert!(book.is_empty());
+        assert_eq!(book.levels().count(), 0);
+    }
+
+    #[test]
+    fn quantity_decrease_keeps_priority() {
+        let mut book = BidBook::new();
+        book.add_order(bid(1, 100, 10)).unwrap();
+        book.add_order(bid(2, 100, 10)).unwrap();
+
+        let Ok(ModifyOutcome::Amended(amended)) = book.modify_order(1, None, Some(4)) else {
+            panic!("expected the order to stay resting");
+        };
+        assert_eq!((amended.id, amended.quantity), (1, 4));
+        assert_eq!(book.get_best_bid().unwrap().id, 1);
+        assert_eq!(book.total_quantity_at(100), 14);
+    }
+
+    #[test]
+    fn reprice_or_increase_loses_priority() {
+        let mut book = BidBook::new();
+        book.add_order(bid(1, 100, 10)).unwrap();
+        book.add_order(bid(2, 100, 10)).unwrap();
+        book.add_order(bid(3, 101, 10)).unwrap();
+
+        book.modify_order(1, None, Some(15)).unwrap();
+        assert_eq!(book.levels().nth(1).unwrap().1.back().unwrap().id, 1);
+        assert_eq!(book.levels().nth(1).unwrap().1.front().unwrap().id, 2);
+
+        book.modify_order(2, Some(101), None).unwrap();
+        assert_eq!(book.total_quantity_at(100), 15);
+        let ids: Vec<u64> = book
+            .levels()
+            .next()
+            .unwrap()
+            .1
+            .iter()
+            .map(|o| o.id)
+            .collect();
+        assert_eq!(ids, vec![3, 2]);
+
+        assert!(matches!(
+            book.modify_order(1, None, Some(0)),
+            Ok(ModifyOutcome::Cancelled(Order {
+                id: 1,
+                quantity: 15,
+                ..
+            }))
+        ));
+        assert_eq!(book.len(), 2);
+        assert_eq!(
+            book.modify_order(1, None, Some(5)),
+            Err(BookError::UnknownId { id: 1 })
+        );
+        assert_eq!(
+            book.modify_order(99, Some(1), None),
+            Err(BookError::UnknownId { id: 99 })
+        );
+    }
+
+    #[test]
+    fn depth_aggregates_top_levels() {
+        let mut book = BidBook::new();
+        assert!(book.depth(5).is_empty());
+
+        book.add_order(bid(1, 100, 10)).unwrap();
+        book.add_order(bid(2, 101, 5)).unwrap();
+        book.add_order(bid(3, 100, 7)).unwrap();
+        book.add_order(bid(4, 99, 1)).unwrap();
+
+        assert_eq!(
+            book.depth(2),
+            vec![
+                PriceLevel {
+                    price: 101,
+                    quantity: 5
+                },
+                PriceLevel {
+                    price: 100,
+                    quantity: 17
+                },
+            ]
+        );
+        assert_eq!(book.depth(10).len(), 3);
+    }
+
+    #[test]
+    fn invariants_hold_through_normal_use() {
+        let mut book = BidBook::new();
+        book.add_order(bid(1, 100, 10)).unwrap();
+        book.add_order(bid(2, 101, 10)).unwrap();
+        book.modify_order(1, Some(102), Some(20)).unwrap();
+        book.remove_order(2);
+        assert_eq!(book.check_invariants(), Ok(()));
+    }
+
+    #[test]
+    fn invariant_checker_catches_corruption() {
+        let mut book = BidBook::new();
+        book.add_order(bid(1, 100, 10)).unwrap();
+        book.inner.levels.get_mut(&100).unwrap()[0].quantity = 0;
+        assert_eq!(
+            book.check_invariants(),
+            Err(BookError::ZeroQuantity { id: 1 })
+        );
+
+        let mut book = BidBook::new();
+        book.add_order(bid(1, 100, 10)).unwrap();
+        book.inner
+            .levels
+            .get_mut(&100)
+            .unwrap()
+            .push_back(bid(1, 100, 5));
+        assert_eq!(
+            book.check_invariants(),
+            Err(BookError::DuplicateId { id: 1 })
+        );
+
+        let mut book = BidBook::new();
+        book.add_order(bid(1, 100, 10)).unwrap();
+        book.inner.levels.get_mut(&100).unwrap()[0].price = 99;
+        assert_eq!(
+            book.check_invariants(),
+            Err(BookError::PriceMismatch { id: 1, level: 100 })
+        );
+
+        let mut book = BidBook::new();
+        book.add_order(bid(1, 100, 10)).unwrap();
+        book.inner.levels.get_mut(&100).unwrap()[0].side = Side::Sell;
+        assert_eq!(book.check_invariants(), Err(BookError::WrongSide { id: 1 }));
+
+        let mut book = BidBook::new();
+        book.add_order(bid(1, 100, 10)).unwrap();
+        book.inner.index.insert(7, 100);
+        assert_eq!(
+            book.check_invariants(),
+            Err(BookError::IndexMismatch { id: 7 })
+        );
+
+        let mut book = BidBook::new();
+        book.add_order(bid(1, 100, 10)).unwrap();
+        book.inner.total_quantity = 3;
+        assert_eq!(
+            book.check_invariants(),
+            Err(BookError::TotalMismatch {
+                recorded: 3,
+                actual: 10
+            })
+        );
+    }
+
+    #[test]
+    fn bucketed_depth_rounds_prices_down() {
+        let mut book = BidBook::new();
+        book.add_order(bid(1, 100, 1)).unwrap();
+        book.add_order(bid(2, 104, 2)).unwrap();
+        book.add_order(bid(3, 105, 4)).unwrap();
+        book.add_order(bid(4, 109, 8)).unwrap();
+        book.add_order(bid(5, 110, 16)).unwrap();
+
+        assert_eq!(
+            book.bucketed_depth(NonZeroU64::new(5).unwrap()),
+            vec![
+                PriceLevel {
+                    price: 110,
+                    quantity: 16
+                },
+                PriceLevel {
+                    price: 105,
+                    quantity: 12
+                },
+                PriceLevel {
+                    price: 100,
+                    quantity: 3
+                },
+            ]
+        );
+        assert_eq!(
+            book.bucketed_depth(NonZeroU64::new(1).unwrap()),
+            book.depth(usize::MAX)
+        );
+    }
+
+    #[test]
+    fn compaction_reclaims_drained_capacity() {
+        let mut book = BidBook::new();
+        for id in 0..1000 {
+            book.add_order(bid(id, 100, 1)).unwrap();
+        }
+        book.add_order(bid(1000, 99, 1)).unwrap();
+        for id in 0..990 {
+            book.remove_order(id);
+        }
+        let grown = book.capacity();
+        assert!(grown.level_slots >= 1000);
+        assert!(grown.index_slots >= 1000);
+
+        let reclaimed = book.compact(25);
+        let after = book.capacity();
+        assert!(reclaimed.level_slots > 0);
+        assert!(reclaimed.index_slots > 0);
+        assert_eq!(after.level_slots, grown.level_slots - reclaimed.level_slots);
+        assert_eq!(after.index_slots, grown.index_slots - reclaimed.index_slots);
+        assert!(after.level_slots < 100);
+        assert!(after.index_slots < 100);
+        assert_eq!(book.get_best_bid().unwrap().id, 990);
+        assert_eq!(book.total_quantity_at(100), 10);
+        assert_eq!(book.check_invariants(), Ok(()));
+
+        assert_eq!(book.compact(0), Capacity::default());
+    }
+
+    #[test]
+    fn bucket_totals_stay_within_side_total() {
+        let mut book = BidBook::new();
+        book.add_order(bid(1, 100, u64::MAX - 1)).unwrap();
+        book.add_order(bid(2, 101, 1)).unwrap();
+        assert!(book.add_order(bid(3, 102, 1)).is_err());
+
+        let width = NonZeroU64::new(10).unwrap();
+        assert_eq!(
+            book.bucketed_depth(width),
+            vec![PriceLevel {
+                price: 100,
+                quantity: u64::MAX
+            }]
+        );
+    }
+
+    #[test]
+    fn rejects_sell_orders() {
+        let mut book = BidBook::new();
+        assert_eq!(
+            book.add_order(ask(1, 100, 5)),
+            Err(BookError::WrongSide { id: 1 })
+        );
+        assert!(book.is_empty());
+        assert!(book.get_best_bid().is_none());
+        assert_eq!(book.check_invariants(), Ok(()));
+    }
+}
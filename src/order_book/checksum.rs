@@ -0,0 +1,111 @@
+use super::{AskBook, BidBook};
+
+const CRC32_POLY: u32 = 0xEDB8_8320;
+
+const CRC32_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ CRC32_POLY
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+};
+
+fn crc32_update(crc: u32, bytes: &[u8]) -> u32 {
+    bytes.iter().fold(crc, |crc, &b| {
+        CRC32_TABLE[((crc ^ u32::from(b)) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+/// CRC32 (IEEE) over the aggregated price levels of both sides.
+///
+/// Only level prices and total quantities are hashed, best level first, so
+/// two books that expose the same depth agree regardless of how individual
+/// orders were inserted, amended or split. Any difference in a level's price
+/// or quantity, or in the number of levels, changes the result.
+pub fn book_checksum(bids: &BidBook, asks: &AskBook) -> u32 {
+    let mut crc = !0u32;
+    for (tag, levels) in [
+        (b'B', bids.depth(usize::MAX)),
+        (b'S', asks.depth(usize::MAX)),
+    ] {
+        crc = crc32_update(crc, &[tag]);
+        crc = crc32_update(crc, &(levels.len() as u64).to_le_bytes());
+        for level in levels {
+            crc = crc32_update(crc, &level.price.to_le_bytes());
+            crc = crc32_update(crc, &level.quantity.to_le_bytes());
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::order_book::fixtures::{ask, bid};
+
+    #[test]
+    fn crc32_matches_reference_value() {
+        assert_eq!(!crc32_update(!0, b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn same_depth_built_differently_agrees() {
+        let mut bids_a = BidBook::new();
+        let mut asks_a = AskBook::new();
+        bids_a.add_order(bid(1, 100, 10)).unwrap();
+        bids_a.add_order(bid(2, 99, 5)).unwrap();
+        asks_a.add_order(ask(3, 101, 8)).unwrap();
+
+        let mut bids_b = BidBook::new();
+        let mut asks_b = AskBook::new();
+        asks_b.add_order(ask(10, 101, 3)).unwrap();
+        asks_b.add_order(ask(11, 101, 5)).unwrap();
+        bids_b.add_order(bid(12, 99, 5)).unwrap();
+        bids_b.add_order(bid(13, 100, 4)).unwrap();
+        bids_b.add_order(bid(14, 98, 6)).unwrap();
+        bids_b.modify_order(14, Some(100), None).unwrap();
+
+        assert_eq!(
+            book_checksum(&bids_a, &asks_a),
+            book_checksum(&bids_b, &asks_b)
+        );
+    }
+
+    #[test]
+    fn any_level_change_alters_checksum() {
+        let mut bids = BidBook::new();
+        let mut asks = AskBook::new();
+        bids.add_order(bid(1, 100, 10)).unwrap();
+        asks.add_order(ask(2, 101, 10)).unwrap();
+        let base = book_checksum(&bids, &asks);
+
+        bids.modify_order(1, None, Some(9)).unwrap();
+        assert_ne!(book_checksum(&bids, &asks), base);
+        bids.modify_order(1, None, Some(10)).unwrap();
+        assert_eq!(book_checksum(&bids, &asks), base);
+
+        asks.modify_order(2, Some(102), None).unwrap();
+        assert_ne!(book_checksum(&bids, &asks), base);
+
+        // The same level on the other side must not collide.
+        let mut swapped_bids = BidBook::new();
+        let mut swapped_asks = AskBook::new();
+        swapped_bids.add_order(bid(1, 100, 10)).unwrap();
+        swapped_bids.add_order(bid(2, 101, 10)).unwrap();
+        assert_ne!(book_checksum(&swapped_bids, &swapped_asks), base);
+        swapped_asks.add_order(ask(3, 1, 1)).unwrap();
+        assert_ne!(book_checksum(&swapped_bids, &swapped_asks), base);
+    }
+}
@@ -0,0 +1,434 @@
+use std::collections::BTreeMap;
+
+use super::{AskBook, BidBook, PriceLevel};
+use crate::types::Side;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LevelAction {
+    Add,
+    Change,
+    Delete,
+}
+
+/// Change to one aggregated price level. `quantity` is the level's new total
+/// (zero for `Delete`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LevelDelta {
+    pub side: Side,
+    pub action: LevelAction,
+    pub price: u64,
+    pub quantity: u64,
+}
+
+/// Market-data message. Every update carries the next sequence number, so a
+/// consumer can tell when it has missed one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BookUpdate {
+    Snapshot {
+        seq: u64,
+        bids: Vec<PriceLevel>,
+        asks: Vec<PriceLevel>,
+    },
+    Delta {
+        seq: u64,
+        changes: Vec<LevelDelta>,
+    },
+}
+
+impl BookUpdate {
+    pub fn seq(&self) -> u64 {
+        match self {
+            BookUpdate::Snapshot { seq, .. } | BookUpdate::Delta { seq, .. } => *seq,
+        }
+    }
+}
+
+/// Publisher side: remembers the last published levels and emits only what
+/// changed since, with a full snapshot every `snapshot_interval` updates.
+#[derive(Debug, Default)]
+pub struct DeltaEncoder {
+    seq: u64,
+    snapshot_interval: u64,
+    since_snapshot: u64,
+    bids: BTreeMap<u64, u64>,
+    asks: BTreeMap<u64, u64>,
+}
+
+impl DeltaEncoder {
+    /// `snapshot_interval` of zero means snapshots are only sent on request.
+    pub fn new(snapshot_interval: u64) -> Self {
+        Self {
+            snapshot_interval,
+            ..Self::default()
+        }
+    }
+
+    /// Encodes the books against the last published state. Returns `None` if
+    /// nothing changed and no periodic snapshot is due.
+    pub fn encode(&mut self, bids: &BidBook, asks: &AskBook) -> Option<BookUpdate> {
+        if self.seq == 0
+            || (self.snapshot_interval > 0 && self.since_snapshot >= self.snapshot_interval)
+        {
+            return Some(self.publish_snapshot(bids, asks));
+        }
+
+        let new_bids = to_map(bids.depth(usize::MAX));
+        let new_asks = to_map(asks.depth(usize::MAX));
+        let mut changes = diff(Side::Buy, &self.bids, &new_bids);
+        changes.extend(diff(Side::Sell, &self.asks, &new_asks));
+        if changes.is_empty() {
+            return None;
+        }
+
+        self.bids = new_bids;
+        self.asks = new_asks;
+        self.seq += 1;
+        self.since_snapshot += 1;
+        Some(BookUpdate::Delta {
+            seq: self.seq,
+            changes,
+        })
+    }
+
+    /// Full snapshot of the last published state, tagged with the current
+    /// sequence number, for a single consumer that needs to resync. It does
+    /// not touch the encoder, so other consumers keep receiving deltas.
+    pub fn snapshot(&self) -> BookUpdate {
+        BookUpdate::Snapshot {
+            seq: self.seq,
+            bids: bid_levels(&self.bids),
+            asks: ask_levels(&self.asks),
+        }
+    }
+
+    /// Periodic snapshot broadcast to every consumer in place of a delta.
+    fn publish_snapshot(&mut self, bids: &BidBook, asks: &AskBook) -> BookUpdate {
+        let bid_levels = bids.depth(usize::MAX);
+        let ask_levels = asks.depth(usize::MAX);
+        self.bids = to_map(bid_levels.clone());
+        self.asks = to_map(ask_levels.clone());
+        self.seq += 1;
+        self.since_snapshot = 0;
+        BookUpdate::Snapshot {
+            seq: self.seq,
+            bids: bid_levels,
+            asks: ask_levels,
+        }
+    }
+}
+
+fn to_map(levels: Vec<PriceLevel>) -> BTreeMap<u64, u64> {
+    levels.into_iter().map(|l| (l.price, l.quantity)).collect()
+}
+
+fn bid_levels(levels: &BTreeMap<u64, u64>) -> Vec<PriceLevel> {
+    levels
+        .iter()
+        .rev()
+        .map(|(&price, &quantity)| PriceLevel { price, quantity })
+        .collect()
+}
+
+fn ask_levels(levels: &BTreeMap<u64, u64>) -> Vec<PriceLevel> {
+    levels
+        .iter()
+        .map(|(&price, &quantity)| PriceLevel { price, quantity })
+        .collect()
+}
+
+fn diff(side: Side, old: &BTreeMap<u64, u64>, new: &BTreeMap<u64, u64>) -> Vec<LevelDelta> {
+    let mut changes = Vec::new();
+    for (&price, &quantity) in new {
+        let action = match old.get(&price) {
+            None => LevelAction::Add,
+            Some(&prev) if prev != quantity => LevelAction::Change,
+            Some(_) => continue,
+        };
+        changes.push(LevelDelta {
+            side,
+            action,
+            price,
+            quantity,
+        });
+    }
+    for &price in old.keys().filter(|p| !new.contains_key(p)) {
+        changes.push(LevelDelta {
+            side,
+            action: LevelAction::Delete,
+            price,
+            quantity: 0,
+        });
+    }
+    changes
+}
+
+/// Returned by [`BookMirror::apply`] when an update cannot be applied; the
+/// consumer should request a snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpdateError {
+    /// A delta does not follow the last applied sequence number.
+    Gap {
+        expected: Option<u64>,
+        received: u64,
+    },
+    /// A snapshot is older than the state already applied.
+    StaleSnapshot { current: u64, received: u64 },
+}
+
+/// Consumer side: a local copy of the aggregated book rebuilt from updates.
+#[derive(Debug, Default)]
+pub struct BookMirror {
+    seq: Option<u64>,
+    bids: BTreeMap<u64, u64>,
+    asks: BTreeMap<u64, u64>,
+}
+
+impl BookMirror {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn apply(&mut self, update: &BookUpdate) -> Result<(), UpdateError> {
+        match update {
+            BookUpdate::Snapshot { seq, bids, asks } => {
+                if let Some(current) = self.seq.filter(|current| seq < current) {
+                    return Err(UpdateError::StaleSnapshot {
+                        current,
+                        received: *seq,
+                    });
+                }
+                self.bids = to_map(bids.clone());
+                self.asks = to_map(asks.clone());
+                self.seq = Some(*seq);
+            }
+            BookUpdate::Delta { seq, changes } => {
+                let expected = self.seq.map(|s| s + 1);
+                if expected != Some(*seq) {
+                    return Err(UpdateError::Gap {
+                        expected,
+                        received: *seq,
+                    });
+                }
+                for change in changes {
+                    let levels = match change.side {
+                        Side::Buy => &mut self.bids,
+                        Side::Sell => &mut self.asks,
+                    };
+                    match change.action {
+                        LevelAction::Add | LevelAction::Change => {
+                            levels.insert(change.price, change.quantity);
+                        }
+                        LevelAction::Delete => {
+                            levels.remove(&change.price);
+                        }
+                    }
+                }
+                self.seq = Some(*seq);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn seq(&self) -> Option<u64> {
+        self.seq
+    }
+
+    /// Bid levels, highest price first.
+    pub fn bids(&self) -> Vec<PriceLevel> {
+        bid_levels(&self.bids)
+    }
+
+    /// Ask levels, lowest price first.
+    pub fn asks(&self) -> Vec<PriceLevel> {
+        ask_levels(&self.asks)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::order_book::fixtures::{ask, bid};
+
+    fn assert_mirrors(mirror: &BookMirror, bids: &BidBook, asks: &AskBook) {
+        assert_eq!(mirror.bids(), bids.depth(usize::MAX));
+        assert_eq!(mirror.asks(), asks.depth(usize::MAX));
+    }
+
+    #[test]
+    fn deltas_keep_mirror_in_sync() {
+        let mut bids = BidBook::new();
+        let mut asks = AskBook::new();
+        let mut encoder = DeltaEncoder::new(0);
+        let mut mirror = BookMirror::new();
+
+        let mut publish = |bids: &BidBook, asks: &AskBook, mirror: &mut BookMirror| {
+            if let Some(update) = encoder.encode(bids, asks) {
+                mirror.apply(&update).unwrap();
+            }
+        };
+
+        publish(&bids, &asks, &mut mirror);
+        assert_eq!(mirror.seq(), Some(1));
+
+        bids.add_order(bid(1, 99, 10)).unwrap();
+        asks.add_order(ask(2, 101, 5)).unwrap();
+        publish(&bids, &asks, &mut mirror);
+        assert_mirrors(&mirror, &bids, &asks);
+
+        bids.add_order(bid(3, 99, 4)).unwrap();
+        asks.add_order(ask(4, 102, 7)).unwrap();
+        publish(&bids, &asks, &mut mirror);
+        assert_mirrors(&mirror, &bids, &asks);
+
+        bids.remove_order(1);
+        asks.remove_order(2);
+        asks.modify_order(4, Some(103), Some(3)).unwrap();
+        publish(&bids, &asks, &mut mirror);
+        assert_mirrors(&mirror, &bids, &asks);
+
+        bids.remove_order(3);
+        publish(&bids, &asks, &mut mirror);
+        assert_mirrors(&mirror, &bids, &asks);
+        assert_eq!(mirror.seq(), Some(5));
+    }
+
+    #[test]
+    fn delta_lists_only_changed_levels() {
+        let mut bids = BidBook::new();
+        let asks = AskBook::new();
+        bids.add_order(bid(1, 99, 10)).unwrap();
+        bids.add_order(bid(2, 98, 10)).unwrap();
+        let mut encoder = DeltaEncoder::new(0);
+        encoder.encode(&bids, &asks);
+
+        assert!(encoder.encode(&bids, &asks).is_none());
+
+        bids.modify_order(1, None, Some(6)).unwrap();
+        bids.add_order(bid(3, 100, 1)).unwrap();
+        bids.remove_order(2);
+        let Some(BookUpdate::Delta { changes, .. }) = encoder.encode(&bids, &asks) else {
+            panic!("expected a delta");
+        };
+        let summary: Vec<(LevelAction, u64, u64)> = changes
+            .iter()
+            .map(|c| (c.action, c.price, c.quantity))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (LevelAction::Change, 99, 6),
+                (LevelAction::Add, 100, 1),
+                (LevelAction::Delete, 98, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn missed_delta_is_detected_and_snapshot_resyncs() {
+        let mut bids = BidBook::new();
+        let asks = AskBook::new();
+        let mut encoder = DeltaEncoder::new(0);
+        let mut mirror = BookMirror::new();
+        mirror
+            .apply(&encoder.encode(&bids, &asks).unwrap())
+            .unwrap();
+
+        bids.add_order(bid(1, 99, 10)).unwrap();
+        let _lost = encoder.encode(&bids, &asks).unwrap();
+        bids.add_order(bid(2, 98, 10)).unwrap();
+        let next = encoder.encode(&bids, &asks).unwrap();
+        assert_eq!(
+            mirror.apply(&next),
+            Err(UpdateError::Gap {
+                expected: Some(2),
+                received: 3
+            })
+        );
+
+        mirror.apply(&encoder.snapshot()).unwrap();
+        assert_mirrors(&mirror, &bids, &asks);
+
+        bids.remove_order(1);
+        mirror
+            .apply(&encoder.encode(&bids, &asks).unwrap())
+            .unwrap();
+        assert_mirrors(&mirror, &bids, &asks);
+    }
+
+    #[test]
+    fn periodic_snapshot_replaces_delta() {
+        let mut bids = BidBook::new();
+        let asks = AskBook::new();
+        let mut encoder = DeltaEncoder::new(2);
+        assert!(matches!(
+            encoder.encode(&bids, &asks),
+            Some(BookUpdate::Snapshot { seq: 1, .. })
+        ));
+
+        for id in 1..=2 {
+            bids.add_order(bid(id, 100 + id, 1)).unwrap();
+            assert!(matches!(
+                encoder.encode(&bids, &asks),
+                Some(BookUpdate::Delta { .. })
+            ));
+        }
+        let update = encoder.encode(&bids, &asks).unwrap();
+        assert!(matches!(update, BookUpdate::Snapshot { .. }));
+        assert_eq!(update.seq(), 4);
+    }
+
+    #[test]
+    fn resync_snapshot_does_not_desync_other_consumers() {
+        let mut bids = BidBook::new();
+        let asks = AskBook::new();
+        let mut encoder = DeltaEncoder::new(0);
+        let mut a = BookMirror::new();
+        let mut b = BookMirror::new();
+        a.apply(&encoder.encode(&bids, &asks).unwrap()).unwrap();
+
+        bids.add_order(bid(1, 99, 10)).unwrap();
+        b.apply(&encoder.snapshot()).unwrap();
+
+        let update = encoder.encode(&bids, &asks).unwrap();
+        assert!(matches!(update, BookUpdate::Delta { seq: 2, .. }));
+        a.apply(&update).unwrap();
+        b.apply(&update).unwrap();
+        assert_mirrors(&a, &bids, &asks);
+        assert_mirrors(&b, &bids, &asks);
+
+        bids.add_order(bid(2, 98, 5)).unwrap();
+        let update = encoder.encode(&bids, &asks).unwrap();
+        a.apply(&update).unwrap();
+        b.apply(&update).unwrap();
+        assert_mirrors(&a, &bids, &asks);
+        assert_mirrors(&b, &bids, &asks);
+    }
+
+    #[test]
+    fn stale_snapshot_is_rejected() {
+        let mut bids = BidBook::new();
+        let asks = AskBook::new();
+        let mut encoder = DeltaEncoder::new(0);
+        let mut mirror = BookMirror::new();
+        mirror
+            .apply(&encoder.encode(&bids, &asks).unwrap())
+            .unwrap();
+        let old = encoder.snapshot();
+
+        bids.add_order(bid(1, 99, 10)).unwrap();
+        mirror
+            .apply(&encoder.encode(&bids, &asks).unwrap())
+            .unwrap();
+
+        assert_eq!(
+            mirror.apply(&old),
+            Err(UpdateError::StaleSnapshot {
+                current: 2,
+                received: 1
+            })
+        );
+        assert_mirrors(&mirror, &bids, &asks);
+        mirror.apply(&encoder.snapshot()).unwrap();
+        assert_eq!(mirror.seq(), Some(2));
+    }
+}
@@ -0,0 +1,147 @@
+use std::collections::HashSet;
+
+use super::{AskBook, BidBook, BookError};
+use crate::types::{Order, Side};
+
+/// Seeds both books with resting orders, e.g. from a reference snapshot at
+/// cold start. Orders are queued in the order given, so within a level the
+/// input order becomes time priority; nothing is matched.
+///
+/// Rejects a batch that repeats an id or would leave the best bid at or above
+/// the best ask before touching either book. Any order `add_order` rejects
+/// (e.g. an id already resting) rolls back the orders loaded so far, so the
+/// books are unchanged on error.
+pub fn load_book(
+    bids: &mut BidBook,
+    asks: &mut AskBook,
+    orders: Vec<Order>,
+) -> Result<(), BookError> {
+    let mut ids = HashSet::with_capacity(orders.len());
+    let mut best_bid = bids.get_best_bid().map(|o| o.price);
+    let mut best_ask = asks.get_best_ask().map(|o| o.price);
+    for order in &orders {
+        if !ids.insert(order.id) {
+            return Err(BookError::DuplicateId { id: order.id });
+        }
+        match order.side {
+            Side::Buy => best_bid = best_bid.max(Some(order.price)),
+            Side::Sell => best_ask = Some(best_ask.map_or(order.price, |p| p.min(order.price))),
+        }
+    }
+    if let (Some(bid), Some(ask)) = (best_bid, best_ask)
+        && bid >= ask
+    {
+        return Err(BookError::Crossed { bid, ask });
+    }
+
+    let mut loaded = Vec::with_capacity(orders.len());
+    for order in orders {
+        let (side, id) = (order.side, order.id);
+        let added = match side {
+            Side::Buy => bids.add_order(order),
+            Side::Sell => asks.add_order(order),
+        };
+        if let Err(err) = added {
+            for (side, id) in loaded {
+                match side {
+                    Side::Buy => bids.remove_order(id),
+                    Side::Sell => asks.remove_order(id),
+                };
+            }
+            return Err(err);
+        }
+        loaded.push((side, id));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::order_book::PriceLevel;
+    use crate::order_book::fixtures::{ask, bid};
+
+    #[test]
+    fn loads_a_thousand_sorted_orders() {
+        // Best first on each side, two orders per level.
+        let mut orders: Vec<Order> = (0..500).map(|i| bid(i, 1000 - i / 2, 1 + i % 3)).collect();
+        orders.extend((500..1000).map(|i| ask(i, 1001 + (i - 500) / 2, 1 + i % 3)));
+        let mut bids = BidBook::new();
+        let mut asks = AskBook::new();
+        load_book(&mut bids, &mut asks, orders).unwrap();
+
+        assert_eq!((bids.len(), asks.len()), (500, 500));
+        assert_eq!(bids.get_best_bid().unwrap().id, 0);
+        assert_eq!(asks.get_best_ask().unwrap().id, 500);
+        assert_eq!(
+            bids.depth(2),
+            vec![
+                PriceLevel {
+                    price: 1000,
+                    quantity: 3
+                },
+                PriceLevel {
+                    price: 999,
+                    quantity: 4
+                },
+            ]
+        );
+        assert_eq!(
+            asks.depth(2),
+            vec![
+                PriceLevel {
+                    price: 1001,
+                    quantity: 4
+                },
+                PriceLevel {
+                    price: 1002,
+                    quantity: 5
+                },
+            ]
+        );
+        assert_eq!(bids.depth(usize::MAX).len(), 250);
+        assert_eq!(asks.depth(usize::MAX).last().unwrap().price, 1250);
+        assert_eq!(bids.check_invariants(), Ok(()));
+        assert_eq!(asks.check_invariants(), Ok(()));
+    }
+
+    #[test]
+    fn rejects_crossing_or_duplicate_input_untouched() {
+        let mut bids = BidBook::new();
+        let mut asks = AskBook::new();
+        asks.add_order(ask(1, 105, 10)).unwrap();
+
+        assert_eq!(
+            load_book(&mut bids, &mut asks, vec![bid(2, 100, 1), bid(3, 105, 1)]),
+            Err(BookError::Crossed { bid: 105, ask: 105 })
+        );
+        assert_eq!(
+            load_book(&mut bids, &mut asks, vec![bid(2, 100, 1), ask(2, 106, 1)]),
+            Err(BookError::DuplicateId { id: 2 })
+        );
+        assert!(bids.is_empty());
+        assert_eq!(asks.len(), 1);
+    }
+
+    #[test]
+    fn rolls_back_when_an_order_is_refused() {
+        let mut bids = BidBook::new();
+        let mut asks = AskBook::new();
+        bids.add_order(bid(1, 99, 10)).unwrap();
+        asks.add_order(ask(2, 105, 10)).unwrap();
+
+        assert_eq!(
+            load_book(
+                &mut bids,
+                &mut asks,
+                vec![bid(3, 99, 5), ask(4, 106, 5), bid(1, 98, 5)]
+            ),
+            Err(BookError::DuplicateId { id: 1 })
+        );
+        assert_eq!((bids.len(), asks.len()), (1, 1));
+        assert_eq!(bids.total_quantity_at(99), 10);
+        assert_eq!(asks.depth(usize::MAX).len(), 1);
+        assert_eq!(bids.check_invariants(), Ok(()));
+        assert_eq!(asks.check_invariants(), Ok(()));
+    }
+}
@@ -0,0 +1,425 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::num::NonZeroU64;
+
+pub mod ask_book;
+pub mod bbo;
+pub mod bid_book;
+pub mod checksum;
+pub mod delta;
+pub mod load;
+pub mod stats;
+
+pub use ask_book::AskBook;
+pub use bbo::{Bbo, BboTracker};
+pub use bid_book::BidBook;
+pub use checksum::book_checksum;
+pub use delta::{BookMirror, BookUpdate, DeltaEncoder, LevelAction, LevelDelta, UpdateError};
+pub use load::load_book;
+pub use stats::{BookStats, book_stats};
+
+use crate::types::{Order, Side};
+
+/// Total resting quantity at a single price.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceLevel {
+    pub price: u64,
+    pub quantity: u64,
+}
+
+/// Reserved slots in a book's containers: queued orders across all price
+/// levels, and entries in the id index.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Capacity {
+    pub level_slots: usize,
+    pub index_slots: usize,
+}
+
+/// Successful outcome of `modify_order`.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ModifyOutcome<'a> {
+    /// The order is still resting, with its new price and/or quantity.
+    Amended(&'a Order),
+    /// A zero quantity was requested, so the order was removed.
+    Cancelled(Order),
+}
+
+/// Rejected book operation, or inconsistency reported by `check_invariants`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BookError {
+    EmptyLevel { price: u64 },
+    ZeroQuantity { id: u64 },
+    PriceMismatch { id: u64, level: u64 },
+    DuplicateId { id: u64 },
+    IndexMismatch { id: u64 },
+    UnknownId { id: u64 },
+    QuantityOverflow { id: u64 },
+    BookFull { id: u64, max_orders: usize },
+    WrongSide { id: u64 },
+    TotalMismatch { recorded: u64, actual: u128 },
+    Crossed { bid: u64, ask: u64 },
+}
+
+impl fmt::Display for BookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BookError::EmptyLevel { price } => write!(f, "empty level at price {price}"),
+            BookError::ZeroQuantity { id } => write!(f, "order {id} rests with zero quantity"),
+            BookError::PriceMismatch { id, level } => {
+                write!(
+                    f,
+                    "order {id} queued at level {level} with a different price"
+                )
+            }
+            BookError::DuplicateId { id } => write!(f, "order id {id} appears more than once"),
+            BookError::IndexMismatch { id } => write!(f, "id index out of sync for order {id}"),
+            BookError::UnknownId { id } => write!(f, "no resting order with id {id}"),
+            BookError::BookFull { id, max_orders } => {
+                write!(
+                    f,
+                    "order {id} rejected: book already holds {max_orders} orders"
+                )
+            }
+            BookError::QuantityOverflow { id } => {
+                write!(f, "order {id} would overflow the book's total quantity")
+            }
+            BookError::WrongSide { id } => write!(f, "order {id} belongs to the other side"),
+            BookError::Crossed { bid, ask } => write!(f, "bid {bid} would cross ask {ask}"),
+            BookError::TotalMismatch { recorded, actual } => {
+                write!(
+                    f,
+                    "side total records {recorded} but its orders sum to {actual}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for BookError {}
+
+/// Price-level storage shared by [`BidBook`] and [`AskBook`]. Levels are kept
+/// in ascending price order; each side picks its own iteration direction.
+#[derive(Debug)]
+struct PriceLevels {
+    /// The only side accepted into these levels.
+    side: Side,
+    levels: BTreeMap<u64, VecDeque<Order>>,
+    /// Order id -> price, so cancels go straight to the right level.
+    index: HashMap<u64, u64>,
+    /// Sum of every resting quantity on this side. Keeping it within `u64`
+    /// means no level, bucket or top-N total can overflow either.
+    total_quantity: u64,
+    /// Most orders allowed to rest on this side at once; `None` means
+    /// unbounded.
+    max_orders: Option<usize>,
+}
+
+impl PriceLevels {
+    fn new(side: Side) -> Self {
+        Self {
+            side,
+            levels: BTreeMap::new(),
+            index: HashMap::new(),
+            total_quantity: 0,
+            max_orders: None,
+        }
+    }
+
+    fn with_max_orders(side: Side, max_orders: usize) -> Self {
+        Self {
+            max_orders: Some(max_orders),
+            ..Self::new(side)
+        }
+    }
+
+    fn add(&mut self, order: Order) -> Result<(), BookError> {
+        if order.side != self.side {
+            return Err(BookError::WrongSide { id: order.id });
+        }
+        if order.quantity == 0 {
+            return Err(BookError::ZeroQuantity { id: order.id });
+        }
+        if self.index.contains_key(&order.id) {
+            return Err(BookError::DuplicateId { id: order.id });
+        }
+        if let Some(max_orders) = self.max_orders.filter(|&max| self.index.len() >= max) {
+            return Err(BookError::BookFull {
+                id: order.id,
+                max_orders,
+            });
+        }
+        self.total_quantity = self
+            .total_quantity
+            .checked_add(order.quantity)
+            .ok_or(BookError::QuantityOverflow { id: order.id })?;
+        self.index.insert(order.id, order.price);
+        self.levels.entry(order.price).or_default().push_back(order);
+        Ok(())
+    }
+
+    fn remove(&mut self, id: u64) -> Option<Order> {
+        let price = self.index.remove(&id)?;
+        let level = self.levels.get_mut(&price)?;
+        let pos = level.iter().position(|o| o.id == id)?;
+        let order = level.remove(pos)?;
+        if level.is_empty() {
+            self.levels.remove(&price);
+        }
+        self.total_quantity -= order.quantity;
+        Some(order)
+    }
+
+    fn modify(
+        &mut self,
+        id: u64,
+        new_price: Option<u64>,
+        new_quantity: Option<u64>,
+    ) -> Result<ModifyOutcome<'_>, BookError> {
+        let price = *self.index.get(&id).ok_or(BookError::UnknownId { id })?;
+        let level = self
+            .levels
+            .get_mut(&price)
+            .ok_or(BookError::IndexMismatch { id })?;
+        let pos = level
+            .iter()
+            .position(|o| o.id == id)
+            .ok_or(BookError::IndexMismatch { id })?;
+        let quantity = level[pos].quantity;
+        let new_price = new_price.unwrap_or(price);
+        let new_quantity = new_quantity.unwrap_or(quantity);
+
+        if new_quantity == 0 {
+            let order = self.remove(id).ok_or(BookError::IndexMismatch { id })?;
+            return Ok(ModifyOutcome::Cancelled(order));
+        }
+        if new_price == price && new_quantity <= quantity {
+            let level = self.levels.get_mut(&price).expect("level checked above");
+            level[pos].quantity = new_quantity;
+            self.total_quantity -= quantity - new_quantity;
+            return Ok(ModifyOutcome::Amended(&level[pos]));
+        }
+
+        (self.total_quantity - quantity)
+            .checked_add(new_quantity)
+            .ok_or(BookError::QuantityOverflow { id })?;
+        let mut order = self.remove(id).ok_or(BookError::IndexMismatch { id })?;
+        order.price = new_price;
+        order.quantity = new_quantity;
+        self.add(order)?;
+        let level = self.levels.get(&new_price).expect("order was just queued");
+        Ok(ModifyOutcome::Amended(
+            level.back().expect("order was just queued"),
+        ))
+    }
+
+    fn total_quantity_at(&self, price: u64) -> u64 {
+        self.levels
+            .get(&price)
+            .map_or(0, |level| level.iter().map(|o| o.quantity).sum())
+    }
+
+    /// Price levels from lowest to highest.
+    fn ascending(&self) -> impl DoubleEndedIterator<Item = (u64, &VecDeque<Order>)> {
+        self.levels.iter().map(|(&price, level)| (price, level))
+    }
+
+    fn check_invariants(&self) -> Result<(), BookError> {
+        let mut seen = HashSet::with_capacity(self.index.len());
+        // Summed wide so a corrupted book is reported rather than overflowing.
+        let mut total: u128 = 0;
+        for (&price, level) in &self.levels {
+            if level.is_empty() {
+                return Err(BookError::EmptyLevel { price });
+            }
+            for order in level {
+                if order.side != self.side {
+                    return Err(BookError::WrongSide { id: order.id });
+                }
+                if order.quantity == 0 {
+                    return Err(BookError::ZeroQuantity { id: order.id });
+                }
+                if order.price != price {
+                    return Err(BookError::PriceMismatch {
+                        id: order.id,
+                        level: price,
+                    });
+                }
+                if !seen.insert(order.id) {
+                    return Err(BookError::DuplicateId { id: order.id });
+                }
+                if self.index.get(&order.id) != Some(&price) {
+                    return Err(BookError::IndexMismatch { id: order.id });
+                }
+                total += u128::from(order.quantity);
+            }
+        }
+        if let Some(&id) = self.index.keys().find(|id| !seen.contains(*id)) {
+            return Err(BookError::IndexMismatch { id });
+        }
+        if total != u128::from(self.total_quantity) {
+            return Err(BookError::TotalMismatch {
+                recorded: self.total_quantity,
+                actual: total,
+            });
+        }
+        Ok(())
+    }
+
+    fn capacity(&self) -> Capacity {
+        Capacity {
+            level_slots: self.levels.values().map(VecDeque::capacity).sum(),
+            index_slots: self.index.capacity(),
+        }
+    }
+
+    fn compact(&mut self, min_utilization_pct: u8) -> Capacity {
+        let pct = usize::from(min_utilization_pct.min(100));
+        let underused = |len: usize, capacity: usize| len * 100 < capacity * pct;
+
+        let before = self.capacity();
+        for level in self.levels.values_mut() {
+            if underused(level.len(), level.capacity()) {
+                level.shrink_to_fit();
+            }
+        }
+        if underused(self.index.len(), self.index.capacity()) {
+            self.index.shrink_to_fit();
+        }
+        let after = self.capacity();
+        Capacity {
+            level_slots: before.level_slots - after.level_slots,
+            index_slots: before.index_slots - after.index_slots,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.index.len()
+    }
+}
+
+/// Sums each of the first `n` levels, in the order given.
+fn aggregate<'a>(
+    levels: impl Iterator<Item = (u64, &'a VecDeque<Order>)>,
+    n: usize,
+) -> Vec<PriceLevel> {
+    levels
+        .take(n)
+        .map(|(price, level)| PriceLevel {
+            price,
+            quantity: level.iter().map(|o| o.quantity).sum(),
+        })
+        .collect()
+}
+
+/// Merges consecutive levels into buckets of `width` ticks, rounding each
+/// price down to `price - price % width`.
+fn bucket<'a>(
+    levels: impl Iterator<Item = (u64, &'a VecDeque<Order>)>,
+    width: NonZeroU64,
+) -> Vec<PriceLevel> {
+    let mut buckets: Vec<PriceLevel> = Vec::new();
+    for (price, level) in levels {
+        let bucket = price - price % width.get();
+        let quantity: u64 = level.iter().map(|o| o.quantity).sum();
+        match buckets.last_mut() {
+            // Cannot overflow: bounded by the side's checked total quantity.
+            Some(last) if last.price == bucket => last.quantity += quantity,
+            _ => buckets.push(PriceLevel {
+                price: bucket,
+                quantity,
+            }),
+        }
+    }
+    buckets
+}
+
+/// Order fixtures shared by the book tests; the timestamp is the id.
+#[cfg(test)]
+mod fixtures {
+    use crate::types::{Order, Side};
+
+    pub(crate) fn bid(id: u64, price: u64, quantity: u64) -> Order {
+        Order::new(id, Side::Buy, price, quantity, id)
+    }
+
+    pub(crate) fn ask(id: u64, price: u64, quantity: u64) -> Order {
+        Order::new(id, Side::Sell, price, quantity, id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fixtures::bid;
+
+    #[test]
+    fn rejects_duplicate_id_and_zero_quantity() {
+        let mut levels = PriceLevels::new(Side::Buy);
+        levels.add(bid(1, 100, 10)).unwrap();
+
+        assert_eq!(
+            levels.add(bid(1, 101, 5)),
+            Err(BookError::DuplicateId { id: 1 })
+        );
+        assert_eq!(
+            levels.add(bid(2, 100, 0)),
+            Err(BookError::ZeroQuantity { id: 2 })
+        );
+        assert_eq!(levels.len(), 1);
+        assert_eq!(levels.levels.len(), 1);
+
+        assert_eq!(levels.remove(1).unwrap().quantity, 10);
+        assert!(levels.levels.is_empty());
+        assert_eq!(levels.check_invariants(), Ok(()));
+    }
+
+    #[test]
+    fn rejects_quantity_that_would_overflow() {
+        let mut levels = PriceLevels::new(Side::Buy);
+        levels.add(bid(1, 100, u64::MAX)).unwrap();
+
+        assert_eq!(
+            levels.add(bid(2, 100, 1)),
+            Err(BookError::QuantityOverflow { id: 2 })
+        );
+        assert_eq!(
+            levels.add(bid(3, 101, 1)),
+            Err(BookError::QuantityOverflow { id: 3 })
+        );
+        assert_eq!(levels.len(), 1);
+        assert_eq!(levels.total_quantity_at(100), u64::MAX);
+        assert_eq!(aggregate(levels.ascending(), 1)[0].quantity, u64::MAX);
+
+        levels.modify(1, None, Some(u64::MAX - 1)).unwrap();
+        levels.add(bid(2, 101, 1)).unwrap();
+        assert_eq!(
+            levels.modify(2, None, Some(2)),
+            Err(BookError::QuantityOverflow { id: 2 })
+        );
+        assert_eq!(levels.total_quantity_at(101), 1);
+        assert_eq!(levels.check_invariants(), Ok(()));
+    }
+
+    #[test]
+    fn full_book_rejects_new_resting_orders() {
+        let mut levels = PriceLevels::with_max_orders(Side::Buy, 3);
+        for id in 1..=3 {
+            levels.add(bid(id, 100 + id, 10)).unwrap();
+        }
+
+        assert_eq!(
+            levels.add(bid(4, 100, 10)),
+            Err(BookError::BookFull {
+                id: 4,
+                max_orders: 3
+            })
+        );
+        assert_eq!(levels.len(), 3);
+
+        levels.modify(1, Some(99), Some(20)).unwrap();
+        levels.remove(2);
+        levels.add(bid(4, 100, 10)).unwrap();
+        assert_eq!(levels.len(), 3);
+        assert_eq!(levels.check_invariants(), Ok(()));
+    }
+}
@@ -0,0 +1,123 @@
+use super::{AskBook, BidBook};
+
+/// Microstructure signals for one book, read straight from the price levels.
+///
+/// Price fields are `None` when the side they need is empty. Quantities only
+/// cover the top `levels` levels of each side.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BookStats {
+    pub best_bid: Option<u64>,
+    pub best_ask: Option<u64>,
+    /// `best_ask - best_bid` in ticks. Signed, since the books do not stop
+    /// the two sides from crossing.
+    pub spread: Option<i128>,
+    /// Midpoint in half-ticks (`best_bid + best_ask`), so a mid that falls
+    /// between two ticks stays exact.
+    pub mid_half_ticks: Option<u128>,
+    pub bid_quantity: u64,
+    pub ask_quantity: u64,
+    /// `(bid_quantity - ask_quantity) / (bid_quantity + ask_quantity)`, from
+    /// -1 (all asks) to 1 (all bids). `None` when both quantities are zero,
+    /// i.e. both sides are empty or `levels` is zero.
+    pub imbalance: Option<f64>,
+}
+
+/// Spread, mid and top-`levels` volume imbalance, computed without copying
+/// the books.
+pub fn book_stats(bids: &BidBook, asks: &AskBook, levels: usize) -> BookStats {
+    let best_bid = bids.get_best_bid().map(|o| o.price);
+    let best_ask = asks.get_best_ask().map(|o| o.price);
+    let (spread, mid_half_ticks) = match (best_bid, best_ask) {
+        (Some(bid), Some(ask)) => (
+            Some(i128::from(ask) - i128::from(bid)),
+            Some(u128::from(bid) + u128::from(ask)),
+        ),
+        _ => (None, None),
+    };
+
+    // Each side's total is kept within u64, so these sums cannot overflow.
+    let bid_quantity: u64 = bids
+        .levels()
+        .take(levels)
+        .flat_map(|(_, level)| level.iter().map(|o| o.quantity))
+        .sum();
+    let ask_quantity: u64 = asks
+        .levels()
+        .take(levels)
+        .flat_map(|(_, level)| level.iter().map(|o| o.quantity))
+        .sum();
+    let total = bid_quantity as f64 + ask_quantity as f64;
+    let imbalance = (total > 0.0).then(|| (bid_quantity as f64 - ask_quantity as f64) / total);
+
+    BookStats {
+        best_bid,
+        best_ask,
+        spread,
+        mid_half_ticks,
+        bid_quantity,
+        ask_quantity,
+        imbalance,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::order_book::fixtures::{ask, bid};
+
+    #[test]
+    fn asymmetric_book_imbalance() {
+        let mut bids = BidBook::new();
+        let mut asks = AskBook::new();
+        bids.add_order(bid(1, 100, 30)).unwrap();
+        bids.add_order(bid(2, 99, 10)).unwrap();
+        bids.add_order(bid(3, 98, 100)).unwrap();
+        asks.add_order(ask(4, 103, 10)).unwrap();
+
+        let stats = book_stats(&bids, &asks, 2);
+        assert_eq!(stats.best_bid, Some(100));
+        assert_eq!(stats.best_ask, Some(103));
+        assert_eq!(stats.spread, Some(3));
+        assert_eq!(stats.mid_half_ticks, Some(203));
+        assert_eq!((stats.bid_quantity, stats.ask_quantity), (40, 10));
+        assert_eq!(stats.imbalance, Some(0.6));
+
+        assert_eq!(book_stats(&bids, &asks, 1).imbalance, Some(0.5));
+
+        let none = book_stats(&bids, &asks, 0);
+        assert_eq!((none.bid_quantity, none.ask_quantity), (0, 0));
+        assert_eq!(none.imbalance, None);
+        assert_eq!(none.spread, Some(3));
+    }
+
+    #[test]
+    fn one_sided_and_empty_books() {
+        let mut bids = BidBook::new();
+        let asks = AskBook::new();
+        let empty = book_stats(&bids, &asks, 5);
+        assert_eq!(empty.spread, None);
+        assert_eq!(empty.mid_half_ticks, None);
+        assert_eq!(empty.imbalance, None);
+
+        bids.add_order(bid(1, 100, 10)).unwrap();
+        let stats = book_stats(&bids, &asks, 5);
+        assert_eq!(stats.best_bid, Some(100));
+        assert_eq!(stats.best_ask, None);
+        assert_eq!(stats.spread, None);
+        assert_eq!(stats.mid_half_ticks, None);
+        assert_eq!((stats.bid_quantity, stats.ask_quantity), (10, 0));
+        assert_eq!(stats.imbalance, Some(1.0));
+    }
+
+    #[test]
+    fn mid_is_exact_for_large_prices() {
+        let mut bids = BidBook::new();
+        let mut asks = AskBook::new();
+        bids.add_order(bid(1, u64::MAX - 1, 1)).unwrap();
+        asks.add_order(ask(2, u64::MAX, 1)).unwrap();
+
+        let stats = book_stats(&bids, &asks, 1);
+        assert_eq!(stats.spread, Some(1));
+        assert_eq!(stats.mid_half_ticks, Some(2 * u128::from(u64::MAX) - 1));
+    }
+}
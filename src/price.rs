@@ -0,0 +1,171 @@
+use std::fmt;
+use std::num::NonZeroU64;
+
+/// Maps a symbol's integer tick prices to decimal display strings.
+///
+/// One tick is `tick_size / 10^decimals` display units, so a 0.05 tick shown
+/// to two places has a `tick_size` of 5 and 2 `decimals`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PriceFormat {
+    tick_size: NonZeroU64,
+    decimals: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceParseError {
+    /// Not a plain non-negative decimal number.
+    Invalid,
+    /// More significant fractional digits than the display precision.
+    TooPrecise,
+    /// Representable, but not a whole number of ticks.
+    NotTickAligned,
+    /// Does not fit in a u64 tick count.
+    Overflow,
+}
+
+impl fmt::Display for PriceParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            PriceParseError::Invalid => "not a valid decimal price",
+            PriceParseError::TooPrecise => "price has more decimals than allowed",
+            PriceParseError::NotTickAligned => "price is not a multiple of the tick size",
+            PriceParseError::Overflow => "price is out of range",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl std::error::Error for PriceParseError {}
+
+impl PriceFormat {
+    /// Returns `None` if `decimals` is above 18, the most a `u64` price can
+    /// carry.
+    pub fn new(tick_size: NonZeroU64, decimals: u32) -> Option<Self> {
+        (decimals <= 18).then_some(Self {
+            tick_size,
+            decimals,
+        })
+    }
+
+    fn scale(&self) -> u128 {
+        10u128.pow(self.decimals)
+    }
+
+    /// Renders `ticks` with exactly `decimals` fractional digits, so equal
+    /// prices always produce identical strings (e.g. "100.50", not "100.5").
+    pub fn format(&self, ticks: u64) -> String {
+        let value = u128::from(ticks) * u128::from(self.tick_size.get());
+        let (int, frac) = (value / self.scale(), value % self.scale());
+        if self.decimals == 0 {
+            int.to_string()
+        } else {
+            format!("{int}.{frac:0width$}", width = self.decimals as usize)
+        }
+    }
+
+    /// Parses a decimal string into ticks. Trailing zeros beyond the display
+    /// precision are accepted, so "100.5", "100.50" and "100.500" agree.
+    pub fn parse(&self, s: &str) -> Result<u64, PriceParseError> {
+        let (int, frac) = match s.split_once('.') {
+            Some((_, "")) => return Err(PriceParseError::Invalid),
+            Some(parts) => parts,
+            None => (s, ""),
+        };
+        let is_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+        if int.is_empty() || !is_digits(int) || !is_digits(frac) {
+            return Err(PriceParseError::Invalid);
+        }
+
+        let decimals = self.decimals as usize;
+        let (frac, excess) = frac.split_at(frac.len().min(decimals));
+        if excess.bytes().any(|b| b != b'0') {
+            return Err(PriceParseError::TooPrecise);
+        }
+
+        let int: u128 = int.parse().map_err(|_| PriceParseError::Overflow)?;
+        // At most `decimals` (<= 18) validated digits, so this cannot overflow.
+        let frac = frac
+            .bytes()
+            .fold(0u128, |acc, b| acc * 10 + u128::from(b - b'0'))
+            * 10u128.pow((decimals - frac.len()) as u32);
+        let value = int
+            .checked_mul(self.scale())
+            .and_then(|v| v.checked_add(frac))
+            .ok_or(PriceParseError::Overflow)?;
+
+        if value % u128::from(self.tick_size.get()) != 0 {
+            return Err(PriceParseError::NotTickAligned);
+        }
+        u64::try_from(value / u128::from(self.tick_size.get()))
+            .map_err(|_| PriceParseError::Overflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn price_format(tick_size: u64, decimals: u32) -> PriceFormat {
+        PriceFormat::new(NonZeroU64::new(tick_size).unwrap(), decimals).unwrap()
+    }
+
+    #[test]
+    fn formats_several_tick_sizes() {
+        assert_eq!(price_format(1, 2).format(10025), "100.25");
+        assert_eq!(price_format(5, 2).format(2010), "100.50");
+        assert_eq!(price_format(25, 2).format(1), "0.25");
+        assert_eq!(price_format(1, 0).format(42), "42");
+        assert_eq!(price_format(5, 3).format(3), "0.015");
+    }
+
+    #[test]
+    fn submitted_price_round_trips() {
+        let fmt = price_format(1, 2);
+        for s in ["100.25", "0.01", "7.00"] {
+            assert_eq!(fmt.format(fmt.parse(s).unwrap()), s);
+        }
+    }
+
+    #[test]
+    fn trailing_zeros_normalize() {
+        let fmt = price_format(5, 2);
+        assert_eq!(fmt.parse("100.5"), Ok(2010));
+        assert_eq!(fmt.parse("100.50"), Ok(2010));
+        assert_eq!(fmt.parse("100.500"), Ok(2010));
+        assert_eq!(fmt.parse("100"), Ok(2000));
+        assert_eq!(fmt.format(2010), "100.50");
+
+        let whole = price_format(1, 0);
+        assert_eq!(whole.parse("42"), Ok(42));
+        assert_eq!(whole.parse("42.00"), Ok(42));
+        assert_eq!(whole.parse("42.5"), Err(PriceParseError::TooPrecise));
+    }
+
+    #[test]
+    fn rejects_bad_prices() {
+        let fmt = price_format(5, 2);
+        assert_eq!(fmt.parse("100.52"), Err(PriceParseError::NotTickAligned));
+        assert_eq!(fmt.parse("100.251"), Err(PriceParseError::TooPrecise));
+        assert_eq!(fmt.parse("-1.00"), Err(PriceParseError::Invalid));
+        assert_eq!(fmt.parse(".5"), Err(PriceParseError::Invalid));
+        assert_eq!(fmt.parse("100."), Err(PriceParseError::Invalid));
+        assert_eq!(
+            price_format(1, 0).parse("7."),
+            Err(PriceParseError::Invalid)
+        );
+        assert_eq!(fmt.parse("1e3"), Err(PriceParseError::Invalid));
+        assert_eq!(
+            fmt.parse("999999999999999999999"),
+            Err(PriceParseError::Overflow)
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_precision() {
+        let tick = NonZeroU64::new(1).unwrap();
+        assert!(PriceFormat::new(tick, 19).is_none());
+        let finest = PriceFormat::new(tick, 18).unwrap();
+        assert_eq!(finest.format(u64::MAX), "18.446744073709551615");
+        assert_eq!(finest.parse("18.446744073709551615"), Ok(u64::MAX));
+    }
+}
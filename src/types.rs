@@ -0,0 +1,28 @@
+/// Side of the book an order rests on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    Buy,
+    Sell,
+}
+
+/// A limit order. Prices are integer ticks so they can key a `BTreeMap`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Order {
+    pub id: u64,
+    pub side: Side,
+    pub price: u64,
+    pub quantity: u64,
+    pub timestamp: u64,
+}
+
+impl Order {
+    pub fn new(id: u64, side: Side, price: u64, quantity: u64, timestamp: u64) -> Self {
+        Self {
+            id,
+            side,
+            price,
+            quantity,
+            timestamp,
+        }
+    }
+}